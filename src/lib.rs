@@ -0,0 +1,4 @@
+pub mod configuration;
+pub mod routes;
+pub mod rpc;
+pub mod startup;
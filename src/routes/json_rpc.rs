@@ -1,183 +1,189 @@
 use actix_web::{web, HttpResponse};
-use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize, Serialize)]
-pub struct GreetingParams {
-    name: String,
-}
+use crate::rpc::{self, MethodRegistry};
 
-#[derive(Debug, Deserialize, Serialize)]
-pub struct GreetingRequest {
-    id: String,
-    jsonrpc: String,
-    method: String,
-    params: GreetingParams,
+pub async fn json_rpc_handler(
+    body: web::Bytes,
+    registry: web::Data<MethodRegistry>,
+) -> HttpResponse {
+    match rpc::handle_payload(&registry, &body) {
+        Some(response) => HttpResponse::Ok().json(response),
+        None => HttpResponse::NoContent().finish(),
+    }
 }
 
-#[derive(Debug, Serialize)]
-pub struct GreetingResponse {
-    id: String,
-    jsonrpc: String,
-    result: GreetingResult,
-}
+#[cfg(test)]
+mod tests {
+    use super::json_rpc_handler;
+    use crate::rpc;
+    use actix_web::{body::to_bytes, dev::Service, http, test, web, App};
+    use serde_json::json;
 
-#[derive(Debug, Serialize)]
-pub struct GreetingResult {
-    greeting: String,
-}
+    fn app_data() -> web::Data<rpc::MethodRegistry> {
+        web::Data::new(rpc::build_registry())
+    }
 
-#[derive(Debug, Serialize)]
-pub struct MethodNotFoundError {
-    code: i32,
-    message: String,
-}
+    #[actix_web::test]
+    async fn test_happy_path() {
+        let app = test::init_service(
+            App::new()
+                .app_data(app_data())
+                .service(web::resource("/").route(web::post().to(json_rpc_handler))),
+        )
+        .await;
 
-#[derive(Debug, Serialize)]
-pub struct MethodNotFoundErrorResponse {
-    error: MethodNotFoundError,
-    id: String,
-    jsonrpc: String,
-}
+        let req = test::TestRequest::post()
+            .uri("/")
+            .set_json(json!({
+                "id": "00000000-0000-0000-0000-000000000000",
+                "jsonrpc": "2.0",
+                "method": "greeting",
+                "params": {"name": "Oliver"},
+            }))
+            .to_request();
+        let resp = app.call(req).await.unwrap();
 
-pub async fn json_rpc_handler(item: web::Json<GreetingRequest>) -> HttpResponse {
-    match item.method.as_str() {
-        "greeting" => {
-            let mut name = item.params.name.trim();
-            if name.is_empty() {
-                name = "World";
-            }
-            let greeting = format!("Hello, {name}!");
-            let response = GreetingResponse {
-                id: item.id.clone(),
-                jsonrpc: item.jsonrpc.clone(),
-                result: GreetingResult { greeting },
-            };
-
-            HttpResponse::Ok().json(response)
-        }
-        _ => {
-            let response = MethodNotFoundErrorResponse {
-                error: MethodNotFoundError {
-                    code: -32601,
-                    message: "Method not found".to_string(),
-                },
-                id: item.id.clone(),
-                jsonrpc: item.jsonrpc.clone(),
-            };
+        assert_eq!(resp.status(), http::StatusCode::OK);
 
-            HttpResponse::Ok().json(response)
-        }
+        let body_bytes = to_bytes(resp.into_body()).await.unwrap();
+        assert_eq!(
+            body_bytes,
+            r##"{"id":"00000000-0000-0000-0000-000000000000","jsonrpc":"2.0","result":{"greeting":"Hello, Oliver!"}}"##
+        );
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::json_rpc_handler;
-    use crate::routes::{GreetingParams, GreetingRequest, GreetingResponse, GreetingResult};
-    use actix_web::{body::to_bytes, dev::Service, http, test, web, App};
+    #[actix_web::test]
+    async fn test_non_existant_method() {
+        let app = test::init_service(
+            App::new()
+                .app_data(app_data())
+                .service(web::resource("/").route(web::post().to(json_rpc_handler))),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/")
+            .set_json(json!({
+                "id": "00000000-0000-0000-0000-000000000000",
+                "jsonrpc": "2.0",
+                "method": "wrong",
+                "params": {"name": "Oliver"},
+            }))
+            .to_request();
+        let resp = app.call(req).await.unwrap();
+
+        assert_eq!(resp.status(), http::StatusCode::OK);
+
+        let body_bytes = to_bytes(resp.into_body()).await.unwrap();
+        assert_eq!(
+            body_bytes,
+            r##"{"error":{"code":-32601,"message":"Method not found"},"id":"00000000-0000-0000-0000-000000000000","jsonrpc":"2.0"}"##
+        );
+    }
 
     #[actix_web::test]
-    async fn test_happy_paths() {
+    async fn test_batch_request() {
         let app = test::init_service(
-            App::new().service(web::resource("/").route(web::post().to(json_rpc_handler))),
+            App::new()
+                .app_data(app_data())
+                .service(web::resource("/").route(web::post().to(json_rpc_handler))),
         )
         .await;
 
-        let key_values = vec![("", "Hello, World!"), ("Oliver", "Hello, Oliver!")];
-
-        for key_value in key_values {
-            let req = test::TestRequest::post()
-                .uri("/")
-                .set_json(GreetingRequest {
-                    id: "00000000-0000-0000-0000-000000000000".to_owned(),
-                    jsonrpc: "2.0".to_owned(),
-                    method: "greeting".to_owned(),
-                    params: GreetingParams {
-                        name: key_value.0.to_owned(),
-                    },
-                })
-                .to_request();
-            let resp = app.call(req).await.unwrap();
-
-            assert_eq!(resp.status(), http::StatusCode::OK);
-
-            let result = GreetingResponse {
-                id: "00000000-0000-0000-0000-000000000000".to_owned(),
-                jsonrpc: "2.0".to_owned(),
-                result: GreetingResult {
-                    greeting: key_value.1.to_owned(),
+        let req = test::TestRequest::post()
+            .uri("/")
+            .set_json(json!([
+                {
+                    "id": "00000000-0000-0000-0000-000000000001",
+                    "jsonrpc": "2.0",
+                    "method": "greeting",
+                    "params": {"name": "Oliver"},
                 },
-            };
+                {
+                    "id": "00000000-0000-0000-0000-000000000002",
+                    "jsonrpc": "2.0",
+                    "method": "wrong",
+                    "params": {"name": "Oliver"},
+                },
+            ]))
+            .to_request();
+        let resp = app.call(req).await.unwrap();
 
-            let actual = to_bytes(resp.into_body()).await.unwrap();
-            let expected = serde_json::to_string(&result).unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
 
-            assert_eq!(actual, expected);
-        }
+        let body_bytes = to_bytes(resp.into_body()).await.unwrap();
+        assert_eq!(
+            body_bytes,
+            r##"[{"id":"00000000-0000-0000-0000-000000000001","jsonrpc":"2.0","result":{"greeting":"Hello, Oliver!"}},{"error":{"code":-32601,"message":"Method not found"},"id":"00000000-0000-0000-0000-000000000002","jsonrpc":"2.0"}]"##
+        );
     }
 
     #[actix_web::test]
-    async fn test_other_possibilities() {
+    async fn test_empty_batch_request() {
         let app = test::init_service(
-            App::new().service(web::resource("/").route(web::post().to(json_rpc_handler))),
+            App::new()
+                .app_data(app_data())
+                .service(web::resource("/").route(web::post().to(json_rpc_handler))),
         )
         .await;
 
-        let key_values = vec![
-            (" ", "Hello, World!"),
-            ("Oliver ", "Hello, Oliver!"),
-            (" Oliver", "Hello, Oliver!"),
-            (" Oliver ", "Hello, Oliver!"),
-        ];
-
-        for key_value in key_values {
-            let req = test::TestRequest::post()
-                .uri("/")
-                .set_json(GreetingRequest {
-                    id: "00000000-0000-0000-0000-000000000000".to_owned(),
-                    jsonrpc: "2.0".to_owned(),
-                    method: "greeting".to_owned(),
-                    params: GreetingParams {
-                        name: key_value.0.to_owned(),
-                    },
-                })
-                .to_request();
-            let resp = app.call(req).await.unwrap();
-
-            assert_eq!(resp.status(), http::StatusCode::OK);
-
-            let result = GreetingResponse {
-                id: "00000000-0000-0000-0000-000000000000".to_owned(),
-                jsonrpc: "2.0".to_owned(),
-                result: GreetingResult {
-                    greeting: key_value.1.to_owned(),
-                },
-            };
+        let req = test::TestRequest::post()
+            .uri("/")
+            .set_json(json!([]))
+            .to_request();
+        let resp = app.call(req).await.unwrap();
 
-            let actual = to_bytes(resp.into_body()).await.unwrap();
-            let expected = serde_json::to_string(&result).unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
 
-            assert_eq!(actual, expected);
-        }
+        let body_bytes = to_bytes(resp.into_body()).await.unwrap();
+        assert_eq!(
+            body_bytes,
+            r##"{"error":{"code":-32600,"message":"Invalid Request"},"id":null,"jsonrpc":"2.0"}"##
+        );
     }
 
     #[actix_web::test]
-    async fn test_non_existant_method() {
+    async fn test_notification_gets_no_response() {
         let app = test::init_service(
-            App::new().service(web::resource("/").route(web::post().to(json_rpc_handler))),
+            App::new()
+                .app_data(app_data())
+                .service(web::resource("/").route(web::post().to(json_rpc_handler))),
         )
         .await;
 
         let req = test::TestRequest::post()
             .uri("/")
-            .set_json(&GreetingRequest {
-                id: "00000000-0000-0000-0000-000000000000".to_owned(),
-                jsonrpc: "2.0".to_owned(),
-                method: "wrong".to_owned(),
-                params: GreetingParams {
-                    name: "Oliver".to_owned(),
-                },
-            })
+            .set_json(json!({
+                "jsonrpc": "2.0",
+                "method": "greeting",
+                "params": {"name": "Oliver"},
+            }))
+            .to_request();
+        let resp = app.call(req).await.unwrap();
+
+        assert_eq!(resp.status(), http::StatusCode::NO_CONTENT);
+
+        let body_bytes = to_bytes(resp.into_body()).await.unwrap();
+        assert!(body_bytes.is_empty());
+    }
+
+    #[actix_web::test]
+    async fn test_explicit_null_id_is_dispatched_and_echoed_back() {
+        let app = test::init_service(
+            App::new()
+                .app_data(app_data())
+                .service(web::resource("/").route(web::post().to(json_rpc_handler))),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/")
+            .set_json(json!({
+                "jsonrpc": "2.0",
+                "id": null,
+                "method": "greeting",
+                "params": {"name": "Oliver"},
+            }))
             .to_request();
         let resp = app.call(req).await.unwrap();
 
@@ -186,7 +192,60 @@ mod tests {
         let body_bytes = to_bytes(resp.into_body()).await.unwrap();
         assert_eq!(
             body_bytes,
-            r##"{"error":{"code":-32601,"message":"Method not found"},"id":"00000000-0000-0000-0000-000000000000","jsonrpc":"2.0"}"##
+            r##"{"id":null,"jsonrpc":"2.0","result":{"greeting":"Hello, Oliver!"}}"##
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_malformed_json_is_a_parse_error() {
+        let app = test::init_service(
+            App::new()
+                .app_data(app_data())
+                .service(web::resource("/").route(web::post().to(json_rpc_handler))),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/")
+            .insert_header(("content-type", "application/json"))
+            .set_payload(r#"{"id": "1", "jsonrpc": "2.0", "method": "greeting""#)
+            .to_request();
+        let resp = app.call(req).await.unwrap();
+
+        assert_eq!(resp.status(), http::StatusCode::OK);
+
+        let body_bytes = to_bytes(resp.into_body()).await.unwrap();
+        assert_eq!(
+            body_bytes,
+            r##"{"error":{"code":-32700,"message":"Parse error"},"id":null,"jsonrpc":"2.0"}"##
         );
     }
+
+    #[actix_web::test]
+    async fn test_missing_params_is_invalid_params() {
+        let app = test::init_service(
+            App::new()
+                .app_data(app_data())
+                .service(web::resource("/").route(web::post().to(json_rpc_handler))),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/")
+            .set_json(json!({
+                "id": "00000000-0000-0000-0000-000000000000",
+                "jsonrpc": "2.0",
+                "method": "greeting",
+            }))
+            .to_request();
+        let resp = app.call(req).await.unwrap();
+
+        assert_eq!(resp.status(), http::StatusCode::OK);
+
+        let body_bytes = to_bytes(resp.into_body()).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+
+        assert_eq!(body["error"]["code"], -32602);
+        assert_eq!(body["error"]["message"], "Invalid params");
+    }
 }
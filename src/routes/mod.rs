@@ -0,0 +1,5 @@
+mod json_rpc;
+mod ws;
+
+pub use json_rpc::json_rpc_handler;
+pub use ws::ws_handler;
@@ -0,0 +1,41 @@
+use actix::{Actor, StreamHandler};
+use actix_web::{web, Error, HttpRequest, HttpResponse};
+use actix_web_actors::ws;
+
+use crate::rpc::{self, MethodRegistry};
+
+/// One actor per open socket. Each text frame is handled independently
+/// through the same [`rpc::handle_payload`] the HTTP transport uses, so a
+/// client gets identical batch/notification/error semantics over either one.
+pub struct JsonRpcSession {
+    registry: web::Data<MethodRegistry>,
+}
+
+impl Actor for JsonRpcSession {
+    type Context = ws::WebsocketContext<Self>;
+}
+
+impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for JsonRpcSession {
+    fn handle(&mut self, msg: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
+        let text = match msg {
+            Ok(ws::Message::Text(text)) => text,
+            Ok(ws::Message::Ping(msg)) => {
+                ctx.pong(&msg);
+                return;
+            }
+            _ => return,
+        };
+
+        if let Some(response) = rpc::handle_payload(&self.registry, text.as_bytes()) {
+            ctx.text(response.to_string());
+        }
+    }
+}
+
+pub async fn ws_handler(
+    req: HttpRequest,
+    stream: web::Payload,
+    registry: web::Data<MethodRegistry>,
+) -> Result<HttpResponse, Error> {
+    ws::start(JsonRpcSession { registry }, &req, stream)
+}
@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{RpcError, RpcMethod};
+
+#[derive(Debug, Deserialize)]
+struct GreetingParams {
+    name: String,
+}
+
+#[derive(Debug, Serialize)]
+struct GreetingResult {
+    greeting: String,
+}
+
+pub struct GreetingMethod;
+
+impl RpcMethod for GreetingMethod {
+    fn call(&self, params: Value) -> Result<Value, RpcError> {
+        let params: GreetingParams = serde_json::from_value(params)
+            .map_err(|error| RpcError::invalid_params(error.to_string()))?;
+
+        let mut name = params.name.trim();
+        if name.is_empty() {
+            name = "World";
+        }
+        let greeting = format!("Hello, {name}!");
+
+        Ok(serde_json::to_value(GreetingResult { greeting })
+            .expect("GreetingResult always serializes"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_greets_by_name() {
+        let result = GreetingMethod.call(json!({"name": "Oliver"})).unwrap();
+
+        assert_eq!(result, json!({"greeting": "Hello, Oliver!"}));
+    }
+
+    #[test]
+    fn test_blank_name_greets_world() {
+        let result = GreetingMethod.call(json!({"name": "  "})).unwrap();
+
+        assert_eq!(result, json!({"greeting": "Hello, World!"}));
+    }
+
+    #[test]
+    fn test_missing_name_is_invalid_params() {
+        let error = GreetingMethod.call(json!({})).unwrap_err();
+
+        assert_eq!(serde_json::to_value(&error).unwrap()["code"], -32602);
+    }
+}
@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+mod greeting;
+
+pub use greeting::GreetingMethod;
+
+// JSON-RPC 2.0 permits the id to be a string, a number, or null, and the
+// response must echo back whichever type the request used.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Id {
+    Str(String),
+    Num(i64),
+    Null,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RpcRequest {
+    // Absent for notifications: the server processes these but sends no response.
+    // A *present* `null` is a legal id (distinct from an absent member) that must
+    // still be dispatched and echoed back, so we can't rely on Option<Id>'s default
+    // deserialization, which treats JSON null the same as a missing key.
+    #[serde(default, deserialize_with = "deserialize_present_id")]
+    id: Option<Id>,
+    jsonrpc: String,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+fn deserialize_present_id<'de, D>(deserializer: D) -> Result<Option<Id>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Id::deserialize(deserializer).map(Some)
+}
+
+#[derive(Debug, Serialize)]
+pub struct SuccessResponse {
+    id: Id,
+    jsonrpc: String,
+    result: Value,
+}
+
+// The standard JSON-RPC 2.0 error codes, reused across every method this
+// server exposes.
+#[derive(Debug, Serialize)]
+pub struct RpcError {
+    code: i32,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<Value>,
+}
+
+impl RpcError {
+    pub fn parse_error() -> Self {
+        RpcError {
+            code: -32700,
+            message: "Parse error".to_string(),
+            data: None,
+        }
+    }
+
+    pub fn invalid_request() -> Self {
+        RpcError {
+            code: -32600,
+            message: "Invalid Request".to_string(),
+            data: None,
+        }
+    }
+
+    pub fn method_not_found() -> Self {
+        RpcError {
+            code: -32601,
+            message: "Method not found".to_string(),
+            data: None,
+        }
+    }
+
+    pub fn invalid_params(reason: impl Into<String>) -> Self {
+        RpcError {
+            code: -32602,
+            message: "Invalid params".to_string(),
+            data: Some(Value::String(reason.into())),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorResponse {
+    pub error: RpcError,
+    pub id: Id,
+    pub jsonrpc: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum RpcResponse {
+    Success(SuccessResponse),
+    Error(ErrorResponse),
+}
+
+/// A single JSON-RPC method, looked up by name in a [`MethodRegistry`].
+pub trait RpcMethod {
+    fn call(&self, params: Value) -> Result<Value, RpcError>;
+}
+
+pub type MethodRegistry = HashMap<String, Box<dyn RpcMethod + Send + Sync>>;
+
+pub fn build_registry() -> MethodRegistry {
+    let mut registry: MethodRegistry = HashMap::new();
+    registry.insert("greeting".to_string(), Box::new(GreetingMethod));
+    registry
+}
+
+// Best-effort extraction of the `id` member for error responses raised
+// before (or because) the body could be deserialized into an `RpcRequest`.
+fn best_effort_id(value: &Value) -> Id {
+    value
+        .get("id")
+        .and_then(|id| serde_json::from_value::<Id>(id.clone()).ok())
+        .unwrap_or(Id::Null)
+}
+
+fn dispatch(registry: &MethodRegistry, request: &RpcRequest, id: Id) -> RpcResponse {
+    match registry.get(&request.method) {
+        Some(method) => match method.call(request.params.clone()) {
+            Ok(result) => RpcResponse::Success(SuccessResponse {
+                id,
+                jsonrpc: request.jsonrpc.clone(),
+                result,
+            }),
+            Err(error) => RpcResponse::Error(ErrorResponse {
+                error,
+                id,
+                jsonrpc: request.jsonrpc.clone(),
+            }),
+        },
+        None => RpcResponse::Error(ErrorResponse {
+            error: RpcError::method_not_found(),
+            id,
+            jsonrpc: request.jsonrpc.clone(),
+        }),
+    }
+}
+
+/// Returns `None` when `value` is a well-formed notification (no response is
+/// ever sent for those), `Some` otherwise.
+fn process_value(registry: &MethodRegistry, value: &Value) -> Option<RpcResponse> {
+    let request = match serde_json::from_value::<RpcRequest>(value.clone()) {
+        Ok(request) => request,
+        Err(_) => {
+            return Some(RpcResponse::Error(ErrorResponse {
+                error: RpcError::invalid_request(),
+                id: best_effort_id(value),
+                jsonrpc: "2.0".to_string(),
+            }));
+        }
+    };
+
+    let id = request.id.clone()?;
+
+    Some(dispatch(registry, &request, id))
+}
+
+fn invalid_request_value() -> Value {
+    serde_json::to_value(ErrorResponse {
+        error: RpcError::invalid_request(),
+        id: Id::Null,
+        jsonrpc: "2.0".to_string(),
+    })
+    .expect("ErrorResponse always serializes")
+}
+
+/// Parses `payload` as a JSON-RPC 2.0 request (single or batch), dispatches
+/// it through `registry`, and returns the JSON value to send back, if any.
+///
+/// Returns `None` for notifications (and batches made up entirely of
+/// notifications), for which the spec requires no response at all. Shared by
+/// every transport (HTTP POST, WebSocket) so they stay in lockstep.
+pub fn handle_payload(registry: &MethodRegistry, payload: &[u8]) -> Option<Value> {
+    let value = match serde_json::from_slice::<Value>(payload) {
+        Ok(value) => value,
+        Err(_) => {
+            return Some(
+                serde_json::to_value(ErrorResponse {
+                    error: RpcError::parse_error(),
+                    id: Id::Null,
+                    jsonrpc: "2.0".to_string(),
+                })
+                .expect("ErrorResponse always serializes"),
+            );
+        }
+    };
+
+    match value {
+        Value::Array(items) => {
+            if items.is_empty() {
+                return Some(invalid_request_value());
+            }
+
+            let responses: Vec<RpcResponse> =
+                items.iter().filter_map(|item| process_value(registry, item)).collect();
+
+            if responses.is_empty() {
+                None
+            } else {
+                Some(serde_json::to_value(responses).expect("responses always serialize"))
+            }
+        }
+        Value::Object(_) => process_value(registry, &value)
+            .map(|response| serde_json::to_value(response).expect("response always serializes")),
+        _ => Some(invalid_request_value()),
+    }
+}
@@ -0,0 +1,54 @@
+use std::net::TcpListener;
+
+use actix_web::dev::Server;
+use actix_web::{middleware, web, App, HttpServer};
+
+use crate::configuration::Settings;
+use crate::routes::{json_rpc_handler, ws_handler};
+use crate::rpc;
+
+/// The running HTTP server, wired up and bound to a port but not yet polled
+/// to completion. Callers drive it to completion with [`Application::run_until_stopped`].
+pub struct Application {
+    port: u16,
+    server: Server,
+}
+
+impl Application {
+    pub async fn build(configuration: Settings) -> Result<Self, std::io::Error> {
+        let address = format!(
+            "{}:{}",
+            configuration.application.host, configuration.application.port
+        );
+        let listener = TcpListener::bind(address)?;
+        let port = listener.local_addr()?.port();
+        let server = run(listener)?;
+
+        Ok(Self { port, server })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    pub async fn run_until_stopped(self) -> Result<(), std::io::Error> {
+        self.server.await
+    }
+}
+
+fn run(listener: TcpListener) -> Result<Server, std::io::Error> {
+    let registry = web::Data::new(rpc::build_registry());
+
+    let server = HttpServer::new(move || {
+        App::new()
+            .wrap(middleware::Logger::default())
+            .app_data(web::PayloadConfig::new(4096))
+            .app_data(registry.clone())
+            .service(web::resource("/").route(web::post().to(json_rpc_handler)))
+            .service(web::resource("/ws").route(web::get().to(ws_handler)))
+    })
+    .listen(listener)?
+    .run();
+
+    Ok(server)
+}